@@ -0,0 +1,151 @@
+// SPDX-FileCopyrightText: 2026 Sébastien Helleu <flashcode@flashtux.org>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Helpers used by [`crate::words::WordPos`] to find the end of a format
+//! placeholder, so its contents aren't tokenized as words.
+
+/// Return the index right after the end of a C conversion spec starting at
+/// `pos` (the byte right after the leading `%`), e.g. `pos` on `"05d"` in
+/// `"%05d"` returns the index after `d`.
+///
+/// Parses, in order: flags (`-+ 0#`), width, precision (`.` + digits),
+/// length modifier (`hh`, `h`, `ll`, `l`, `L`, `z`, `j`, `t`), and finally
+/// the conversion character itself.
+pub fn get_index_end_c_format(bytes: &[u8], mut pos: usize, len: usize) -> usize {
+    while pos < len && matches!(bytes[pos], b'-' | b'+' | b' ' | b'0' | b'#') {
+        pos += 1;
+    }
+    while pos < len && bytes[pos].is_ascii_digit() {
+        pos += 1;
+    }
+    if pos < len && bytes[pos] == b'.' {
+        pos += 1;
+        while pos < len && bytes[pos].is_ascii_digit() {
+            pos += 1;
+        }
+    }
+    if pos + 1 < len
+        && ((bytes[pos] == b'h' && bytes[pos + 1] == b'h')
+            || (bytes[pos] == b'l' && bytes[pos + 1] == b'l'))
+    {
+        pos += 2;
+    } else if pos < len && matches!(bytes[pos], b'h' | b'l' | b'L' | b'z' | b'j' | b't') {
+        pos += 1;
+    }
+    if pos < len {
+        pos += 1;
+    }
+    pos
+}
+
+/// Return the index right after the end of a `python-format` conversion
+/// spec starting at `pos` (the byte right after the leading `%`).
+///
+/// Handles the `%(name)s` mapping-key form in addition to the plain C
+/// conversions (`%d`, `%s`, ...) that `python-format` also allows.
+pub fn get_index_end_python_format(bytes: &[u8], pos: usize, len: usize) -> usize {
+    if pos < len && bytes[pos] == b'(' {
+        let mut end = pos + 1;
+        while end < len && bytes[end] != b')' {
+            end += 1;
+        }
+        if end < len {
+            end += 1; // consume ')'
+        }
+        get_index_end_c_format(bytes, end, len)
+    } else {
+        get_index_end_c_format(bytes, pos, len)
+    }
+}
+
+/// Return the index right after the matching closing brace for a
+/// `python-brace-format` placeholder starting at `pos` (the byte right
+/// after the leading `{`), e.g. `pos` on `"name}"` in `"{name}"` returns
+/// the index after `}`.
+///
+/// Braces are allowed to nest (a format spec can itself contain a nested
+/// replacement field, e.g. `{0:>{width}}`), so this tracks brace depth
+/// rather than stopping at the first `}`.
+pub fn get_index_end_brace_format(bytes: &[u8], pos: usize, len: usize) -> usize {
+    let mut depth = 1;
+    let mut end = pos;
+    while end < len && depth > 0 {
+        match bytes[end] {
+            b'{' => depth += 1,
+            b'}' => depth -= 1,
+            _ => {}
+        }
+        end += 1;
+    }
+    end
+}
+
+/// Return the index right after the end of a `qt-format` placeholder
+/// starting at `pos` (the byte right after the leading `%`), e.g. `pos` on
+/// `"12"` in `"%12"` returns the index after `2`.
+///
+/// Qt placeholders are just a 1-or-2-digit argument number (`%1` to `%99`).
+pub fn get_index_end_qt_format(bytes: &[u8], pos: usize, len: usize) -> usize {
+    let max_end = len.min(pos + 2);
+    let mut end = pos;
+    while end < max_end && bytes[end].is_ascii_digit() {
+        end += 1;
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_c_format_width_precision() {
+        let s = "%05d";
+        assert_eq!(get_index_end_c_format(s.as_bytes(), 1, s.len()), 4);
+    }
+
+    #[test]
+    fn test_c_format_length_modifier() {
+        let s = "%llu";
+        assert_eq!(get_index_end_c_format(s.as_bytes(), 1, s.len()), 4);
+    }
+
+    #[test]
+    fn test_python_format_mapping_key() {
+        let s = "%(name)s";
+        assert_eq!(get_index_end_python_format(s.as_bytes(), 1, s.len()), 8);
+    }
+
+    #[test]
+    fn test_python_format_plain_conversion() {
+        let s = "%d";
+        assert_eq!(get_index_end_python_format(s.as_bytes(), 1, s.len()), 2);
+    }
+
+    #[test]
+    fn test_brace_format_empty() {
+        let s = "{}";
+        assert_eq!(get_index_end_brace_format(s.as_bytes(), 1, s.len()), 2);
+    }
+
+    #[test]
+    fn test_brace_format_named_with_spec() {
+        let s = "{0:>8}";
+        assert_eq!(get_index_end_brace_format(s.as_bytes(), 1, s.len()), 6);
+    }
+
+    #[test]
+    fn test_qt_format() {
+        let s = "%1";
+        assert_eq!(get_index_end_qt_format(s.as_bytes(), 1, s.len()), 2);
+    }
+
+    #[test]
+    fn test_qt_format_caps_at_two_digits() {
+        // Qt placeholders only go up to %99: the "3" here is literal text,
+        // not part of the placeholder.
+        let s = "%123";
+        assert_eq!(get_index_end_qt_format(s.as_bytes(), 1, s.len()), 3);
+    }
+}
@@ -0,0 +1,346 @@
+// SPDX-FileCopyrightText: 2026 Sébastien Helleu <flashcode@flashtux.org>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Hunspell `.aff`/`.dic` dictionary backend: loads stems from the `.dic`
+//! file and affix rules (`PFX`/`SFX` blocks) from the `.aff` file, so that
+//! [`HunspellDict::check`] accepts inflected forms (plurals, conjugations,
+//! ...) that aren't spelled out in the word list itself.
+//!
+//! Only the hunspell default flag encoding is supported: one ASCII character
+//! per flag, with affix/stem flag sets separated by nothing (`SFX S`,
+//! `cat/S`). Dictionaries that declare a `FLAG long`/`FLAG num`/`FLAG UTF-8`
+//! directive use a different encoding and are rejected by [`load_hunspell`]
+//! rather than silently mis-parsed.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// One `PFX`/`SFX` rule: a word is accepted if replacing `add` at its end
+/// (suffix) or start (prefix) with `strip` yields a stem carrying `flag`
+/// and satisfying `condition`.
+struct AffixRule {
+    flag: char,
+    strip: String,
+    add: String,
+    condition: Vec<ConditionAtom>,
+}
+
+/// One position of a hunspell affix condition: `.` matches anything, `[abc]`
+/// / `[^abc]` match a (negated) character class, anything else matches
+/// itself literally.
+enum ConditionAtom {
+    Any,
+    Literal(char),
+    Class { chars: HashSet<char>, negate: bool },
+}
+
+impl ConditionAtom {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            ConditionAtom::Any => true,
+            ConditionAtom::Literal(l) => *l == c,
+            ConditionAtom::Class { chars, negate } => chars.contains(&c) != *negate,
+        }
+    }
+}
+
+fn parse_condition(condition: &str) -> Vec<ConditionAtom> {
+    let chars: Vec<char> = condition.chars().collect();
+    let mut atoms = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '.' => {
+                atoms.push(ConditionAtom::Any);
+                i += 1;
+            }
+            '[' => {
+                let mut j = i + 1;
+                let negate = chars.get(j) == Some(&'^');
+                if negate {
+                    j += 1;
+                }
+                let start = j;
+                while j < chars.len() && chars[j] != ']' {
+                    j += 1;
+                }
+                atoms.push(ConditionAtom::Class {
+                    chars: chars[start..j].iter().copied().collect(),
+                    negate,
+                });
+                i = j + 1;
+            }
+            c => {
+                atoms.push(ConditionAtom::Literal(c));
+                i += 1;
+            }
+        }
+    }
+    atoms
+}
+
+/// Check `condition` against the trailing characters of `stem` (suffixes).
+fn condition_matches_end(condition: &[ConditionAtom], stem: &str) -> bool {
+    let stem: Vec<char> = stem.chars().collect();
+    if condition.len() > stem.len() {
+        return false;
+    }
+    let offset = stem.len() - condition.len();
+    condition
+        .iter()
+        .enumerate()
+        .all(|(i, atom)| atom.matches(stem[offset + i]))
+}
+
+/// Check `condition` against the leading characters of `stem` (prefixes).
+fn condition_matches_start(condition: &[ConditionAtom], stem: &str) -> bool {
+    let stem: Vec<char> = stem.chars().collect();
+    if condition.len() > stem.len() {
+        return false;
+    }
+    condition
+        .iter()
+        .enumerate()
+        .all(|(i, atom)| atom.matches(stem[i]))
+}
+
+/// A hunspell dictionary: stems with their affix flags, plus the prefix and
+/// suffix rules that those flags enable.
+pub struct HunspellDict {
+    stems: HashMap<String, HashSet<char>>,
+    prefixes: HashMap<char, Vec<AffixRule>>,
+    suffixes: HashMap<char, Vec<AffixRule>>,
+}
+
+impl HunspellDict {
+    /// Return `true` if `word` is a known stem, or a valid prefix/suffix
+    /// de-application of one.
+    pub fn check(&self, word: &str) -> bool {
+        self.stems.contains_key(word) || self.check_suffix(word) || self.check_prefix(word)
+    }
+
+    fn check_suffix(&self, word: &str) -> bool {
+        self.suffixes.values().flatten().any(|rule| {
+            word.ends_with(rule.add.as_str()) && {
+                let stem = format!("{}{}", &word[..word.len() - rule.add.len()], rule.strip);
+                self.stems
+                    .get(&stem)
+                    .is_some_and(|flags| flags.contains(&rule.flag))
+                    && condition_matches_end(&rule.condition, &stem)
+            }
+        })
+    }
+
+    fn check_prefix(&self, word: &str) -> bool {
+        self.prefixes.values().flatten().any(|rule| {
+            word.starts_with(rule.add.as_str()) && {
+                let stem = format!("{}{}", rule.strip, &word[rule.add.len()..]);
+                self.stems
+                    .get(&stem)
+                    .is_some_and(|flags| flags.contains(&rule.flag))
+                    && condition_matches_start(&rule.condition, &stem)
+            }
+        })
+    }
+
+    /// Dictionary stems (without inflected forms), for reuse by the
+    /// suggestion/compound machinery in [`super::Dict`].
+    pub fn stems(&self) -> impl Iterator<Item = &String> {
+        self.stems.keys()
+    }
+}
+
+/// Parse `.dic` content: a word-count line, then one `word` or `word/FLAGS`
+/// entry per line.
+fn parse_dic(content: &str) -> HashMap<String, HashSet<char>> {
+    content
+        .lines()
+        .skip(1) // first line is the approximate word count
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() {
+                return None;
+            }
+            let mut parts = line.splitn(2, '/');
+            let word = parts.next()?.trim();
+            if word.is_empty() {
+                return None;
+            }
+            let flags = parts.next().unwrap_or("").chars().collect();
+            Some((word.to_string(), flags))
+        })
+        .collect()
+}
+
+/// Parse `PFX`/`SFX` rule blocks from `.aff` content.
+fn parse_aff(content: &str) -> (HashMap<char, Vec<AffixRule>>, HashMap<char, Vec<AffixRule>>) {
+    let mut prefixes: HashMap<char, Vec<AffixRule>> = HashMap::new();
+    let mut suffixes: HashMap<char, Vec<AffixRule>> = HashMap::new();
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        let header: Vec<&str> = line.split_whitespace().collect();
+        let is_suffix = match header.first() {
+            Some(&"SFX") => true,
+            Some(&"PFX") => false,
+            _ => continue,
+        };
+        let Some(&flag_field) = header.get(1) else {
+            continue;
+        };
+        let Some(flag) = flag_field.chars().next() else {
+            continue;
+        };
+        let count: usize = header.get(3).and_then(|c| c.parse().ok()).unwrap_or(0);
+        for _ in 0..count {
+            let Some(rule_line) = lines.next() else {
+                break;
+            };
+            let fields: Vec<&str> = rule_line.split_whitespace().collect();
+            if fields.len() < 5 {
+                continue;
+            }
+            let strip = if fields[2] == "0" { "" } else { fields[2] };
+            let add_field = fields[3].split('/').next().unwrap_or("");
+            let add = if add_field == "0" { "" } else { add_field };
+            let rule = AffixRule {
+                flag,
+                strip: strip.to_string(),
+                add: add.to_string(),
+                condition: parse_condition(fields[4]),
+            };
+            if is_suffix {
+                suffixes.entry(flag).or_default().push(rule);
+            } else {
+                prefixes.entry(flag).or_default().push(rule);
+            }
+        }
+    }
+    (prefixes, suffixes)
+}
+
+/// Return the encoding named by an `.aff` `FLAG` directive (e.g. `long`,
+/// `num`, `UTF-8`), if any. Its absence means the hunspell default: one
+/// ASCII character per flag, which is all [`parse_dic`]/[`parse_aff`]
+/// understand.
+fn flag_encoding(aff_content: &str) -> Option<&str> {
+    aff_content.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        (fields.next() == Some("FLAG"))
+            .then(|| fields.next())
+            .flatten()
+    })
+}
+
+/// Load a hunspell dictionary from a `.dic`/`.aff` pair.
+///
+/// # Errors
+///
+/// Returns an error if `aff_path` declares a `FLAG` directive: only the
+/// default single-character flag encoding is supported (see the module
+/// documentation).
+pub fn load_hunspell(dic_path: &Path, aff_path: &Path) -> io::Result<HunspellDict> {
+    let aff_content = fs::read_to_string(aff_path)?;
+    if let Some(encoding) = flag_encoding(&aff_content) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "{}: unsupported `FLAG {encoding}` directive, only the default \
+                 single-character flag encoding is supported",
+                aff_path.display()
+            ),
+        ));
+    }
+    let stems = parse_dic(&fs::read_to_string(dic_path)?);
+    let (prefixes, suffixes) = parse_aff(&aff_content);
+    Ok(HunspellDict {
+        stems,
+        prefixes,
+        suffixes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunspell_dict(dic: &str, aff: &str) -> HunspellDict {
+        let stems = parse_dic(dic);
+        let (prefixes, suffixes) = parse_aff(aff);
+        HunspellDict {
+            stems,
+            prefixes,
+            suffixes,
+        }
+    }
+
+    #[test]
+    fn test_check_stem() {
+        let dict = hunspell_dict("1\ncat\n", "");
+        assert!(dict.check("cat"));
+        assert!(!dict.check("cats"));
+    }
+
+    #[test]
+    fn test_check_suffix() {
+        let dict = hunspell_dict(
+            "1\ncat/S\n",
+            "SFX S Y 1\nSFX S 0 s .\n",
+        );
+        assert!(dict.check("cat"));
+        assert!(dict.check("cats"));
+        assert!(!dict.check("cati"));
+    }
+
+    #[test]
+    fn test_check_suffix_with_strip_and_condition() {
+        // "try" + S => "tries" (strip "y", add "ies", only after a consonant)
+        let dict = hunspell_dict(
+            "1\ntry/S\n",
+            "SFX S Y 1\nSFX S y ies [^aeiou]y\n",
+        );
+        assert!(dict.check("tries"));
+        assert!(!dict.check("trys"));
+    }
+
+    #[test]
+    fn test_check_prefix() {
+        let dict = hunspell_dict(
+            "1\nhappy/U\n",
+            "PFX U Y 1\nPFX U 0 un .\n",
+        );
+        assert!(dict.check("happy"));
+        assert!(dict.check("unhappy"));
+    }
+
+    #[test]
+    fn test_check_ignores_rule_without_matching_flag() {
+        let dict = hunspell_dict("1\ncat\n", "SFX S Y 1\nSFX S 0 s .\n");
+        assert!(!dict.check("cats"));
+    }
+
+    #[test]
+    fn test_flag_encoding_default() {
+        assert_eq!(flag_encoding("SFX S Y 1\nSFX S 0 s .\n"), None);
+    }
+
+    #[test]
+    fn test_flag_encoding_long() {
+        assert_eq!(flag_encoding("FLAG long\nSFX S1 Y 1\n"), Some("long"));
+    }
+
+    #[test]
+    fn test_load_hunspell_rejects_non_default_flag_encoding() {
+        let dir = std::env::temp_dir().join("poexam-hunspell-test-flag-long");
+        fs::create_dir_all(&dir).unwrap();
+        let dic_path = dir.join("en.dic");
+        let aff_path = dir.join("en.aff");
+        fs::write(&dic_path, "1\ncat/S1\n").unwrap();
+        fs::write(&aff_path, "FLAG long\nSFX S1 Y 1\nSFX S1 0 s .\n").unwrap();
+
+        let err = load_hunspell(&dic_path, &aff_path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,507 @@
+// SPDX-FileCopyrightText: 2026 Sébastien Helleu <flashcode@flashtux.org>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Dictionary used by the spelling rules to check words and suggest
+//! corrections.
+
+mod hunspell;
+
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub use hunspell::HunspellDict;
+
+/// Default maximum edit distance (Optimal String Alignment) used to find
+/// suggestions.
+const DEFAULT_MAX_EDIT_DISTANCE: usize = 2;
+
+/// Default maximum number of suggestions returned by [`Dict::suggest`].
+const DEFAULT_MAX_SUGGESTIONS: usize = 5;
+
+/// Default minimum length of each part of a compound word, used by
+/// [`Dict::with_accept_compound`] to avoid spurious short-part splits.
+const DEFAULT_MIN_COMPOUND_PART_LEN: usize = 3;
+
+/// Per-language dictionary knobs, threaded from the CLI/config through
+/// [`get_dict`] down to the [`Dict`] it builds. Leaving a field at its
+/// default keeps the corresponding [`Dict`] behavior unchanged.
+#[derive(Clone)]
+pub struct DictOptions {
+    /// Overrides [`DEFAULT_MAX_EDIT_DISTANCE`].
+    pub max_edit_distance: Option<usize>,
+    /// Overrides [`DEFAULT_MAX_SUGGESTIONS`].
+    pub max_suggestions: Option<usize>,
+    /// CLI `--personal-words`: path to an extra word list file, one word
+    /// per line, consulted by [`Dict::check`] before a word is reported as
+    /// misspelled (e.g. project-specific product names or jargon).
+    pub personal_words_path: Option<PathBuf>,
+    /// Case sensitivity applied when loading `personal_words_path`.
+    pub personal_case_sensitive: bool,
+    /// Opt-in per language: accept a word that isn't in the dictionary
+    /// itself but splits into 2+ dictionary words. See
+    /// [`Dict::with_accept_compound`].
+    pub accept_compound: bool,
+    /// Overrides [`DEFAULT_MIN_COMPOUND_PART_LEN`], only used when
+    /// `accept_compound` is set.
+    pub min_compound_part_len: Option<usize>,
+}
+
+impl Default for DictOptions {
+    fn default() -> Self {
+        Self {
+            max_edit_distance: None,
+            max_suggestions: None,
+            personal_words_path: None,
+            accept_compound: false,
+            min_compound_part_len: None,
+            personal_case_sensitive: true,
+        }
+    }
+}
+
+/// A loaded dictionary, used to check whether a word is spelled correctly
+/// and to suggest corrections for misspelled words.
+pub struct Dict {
+    /// Dictionary words, in the order they were loaded (used as a frequency
+    /// rank to break ties between equally-close suggestions). Stems of a
+    /// hunspell dictionary come in no defined order.
+    words: Vec<String>,
+    /// Same words as `words`, for O(1) membership checks.
+    lookup: HashSet<String>,
+    max_edit_distance: usize,
+    max_suggestions: usize,
+    /// Deletion-neighborhood index (SymSpell), built lazily on the first
+    /// call to [`Dict::suggest`] so that rules which never ask for
+    /// suggestions don't pay for building it.
+    deletes: OnceCell<HashMap<String, Vec<usize>>>,
+    /// Per-run personal/ignore word list, consulted by [`Dict::check`]
+    /// before a word is reported as misspelled.
+    personal_words: HashSet<String>,
+    personal_case_sensitive: bool,
+    /// Whether [`Dict::check`] should accept a word that isn't in the
+    /// dictionary itself but splits into 2+ dictionary words. Opt-in per
+    /// language: useful for German-like compounding languages, but can
+    /// cause false negatives in others.
+    accept_compound: bool,
+    min_compound_part_len: usize,
+    /// Optional hunspell affix backend, consulted by [`Dict::check`] for
+    /// inflected forms (plurals, conjugations, ...) not present as-is in
+    /// `lookup`.
+    hunspell: Option<HunspellDict>,
+}
+
+impl Dict {
+    fn new(words: Vec<String>) -> Self {
+        let lookup = words.iter().cloned().collect();
+        Self {
+            words,
+            lookup,
+            max_edit_distance: DEFAULT_MAX_EDIT_DISTANCE,
+            max_suggestions: DEFAULT_MAX_SUGGESTIONS,
+            deletes: OnceCell::new(),
+            personal_words: HashSet::new(),
+            personal_case_sensitive: true,
+            accept_compound: false,
+            min_compound_part_len: DEFAULT_MIN_COMPOUND_PART_LEN,
+            hunspell: None,
+        }
+    }
+
+    /// Attach a hunspell `.aff`/`.dic` affix backend, so [`Dict::check`]
+    /// also accepts inflected forms that match a stem plus a known affix.
+    fn with_hunspell(mut self, hunspell: HunspellDict) -> Self {
+        self.hunspell = Some(hunspell);
+        self
+    }
+
+    /// Set the maximum edit distance (Optimal String Alignment) allowed
+    /// between a misspelled word and a suggestion.
+    pub fn with_max_edit_distance(mut self, max_edit_distance: usize) -> Self {
+        self.max_edit_distance = max_edit_distance;
+        self
+    }
+
+    /// Set the maximum number of suggestions returned by [`Dict::suggest`].
+    pub fn with_max_suggestions(mut self, max_suggestions: usize) -> Self {
+        self.max_suggestions = max_suggestions;
+        self
+    }
+
+    /// Add a personal/ignore word list, consulted by [`Dict::check`] before
+    /// a word is reported as misspelled, e.g. project-specific product
+    /// names or jargon that aren't worth adding to the main dictionary.
+    pub fn with_personal_words(mut self, words: HashSet<String>, case_sensitive: bool) -> Self {
+        self.personal_words = words;
+        self.personal_case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Enable compound-word acceptance: a word that isn't in the dictionary
+    /// is still accepted if it can be split into 2 or more dictionary words,
+    /// each at least `min_part_len` characters long.
+    pub fn with_accept_compound(mut self, enabled: bool, min_part_len: usize) -> Self {
+        self.accept_compound = enabled;
+        self.min_compound_part_len = min_part_len;
+        self
+    }
+
+    /// Return `true` if `word` is spelled correctly: it's in the
+    /// dictionary, in the personal word list, a valid hunspell affix form of
+    /// a stem, or (when enabled) a valid compound of dictionary words.
+    pub fn check(&self, word: &str) -> bool {
+        self.lookup.contains(word)
+            || self.check_personal(word)
+            || self.hunspell.as_ref().is_some_and(|h| h.check(word))
+            || (self.accept_compound && self.is_compound(word))
+    }
+
+    fn check_personal(&self, word: &str) -> bool {
+        if self.personal_case_sensitive {
+            self.personal_words.contains(word)
+        } else {
+            self.personal_words.contains(&word.to_lowercase())
+        }
+    }
+
+    /// Return `true` if `word` segments fully into 2 or more dictionary
+    /// words, each at least `min_compound_part_len` characters long.
+    ///
+    /// Uses a simple word-break DP over the token's characters: `reachable`
+    /// tracks, for each prefix length, whether that prefix can be fully
+    /// segmented into dictionary words.
+    fn is_compound(&self, word: &str) -> bool {
+        let chars: Vec<char> = word.chars().collect();
+        let len = chars.len();
+        let mut reachable = vec![false; len + 1];
+        reachable[0] = true;
+        for end in 1..=len {
+            for start in 0..end {
+                if !reachable[start] || end - start < self.min_compound_part_len {
+                    continue;
+                }
+                let part: String = chars[start..end].iter().collect();
+                if self.lookup.contains(&part) {
+                    reachable[end] = true;
+                    break;
+                }
+            }
+        }
+        reachable[len]
+    }
+
+    /// Suggest corrections for a misspelled `word`, closest match first.
+    ///
+    /// Candidates are found with the SymSpell delete-neighborhood approach:
+    /// the deletion variants (up to `max_edit_distance` deletions) of `word`
+    /// are compared against a precomputed index of the deletion variants of
+    /// every dictionary word, which is much cheaper than scanning the whole
+    /// dictionary. Candidates are then ranked by Optimal String Alignment
+    /// distance to `word`, ties broken by dictionary order.
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        if self.max_edit_distance == 0 || self.check(word) {
+            return Vec::new();
+        }
+        let deletes = self.deletes.get_or_init(|| self.build_deletes());
+        let mut candidates: HashSet<usize> = HashSet::new();
+        for variant in deletes_of(word, self.max_edit_distance) {
+            if let Some(indexes) = deletes.get(&variant) {
+                candidates.extend(indexes);
+            }
+        }
+        let mut ranked: Vec<(usize, usize)> = candidates
+            .into_iter()
+            .filter_map(|index| {
+                let distance = optimal_string_alignment_distance(word, &self.words[index]);
+                (distance <= self.max_edit_distance).then_some((distance, index))
+            })
+            .collect();
+        ranked.sort_unstable();
+        ranked
+            .into_iter()
+            .take(self.max_suggestions)
+            .map(|(_, index)| self.words[index].clone())
+            .collect()
+    }
+
+    /// Build the deletion-neighborhood index used by [`Dict::suggest`].
+    fn build_deletes(&self) -> HashMap<String, Vec<usize>> {
+        let mut deletes: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, word) in self.words.iter().enumerate() {
+            for variant in deletes_of(word, self.max_edit_distance) {
+                deletes.entry(variant).or_default().push(index);
+            }
+        }
+        deletes
+    }
+}
+
+/// Generate every string obtainable from `word` by deleting up to `k`
+/// characters, including `word` itself.
+fn deletes_of(word: &str, k: usize) -> HashSet<String> {
+    let mut variants = HashSet::new();
+    variants.insert(word.to_string());
+    let mut current = variants.clone();
+    for _ in 0..k {
+        let mut next = HashSet::new();
+        for candidate in &current {
+            let chars: Vec<char> = candidate.chars().collect();
+            for skip in 0..chars.len() {
+                let deleted: String = chars
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| i != skip)
+                    .map(|(_, c)| *c)
+                    .collect();
+                if variants.insert(deleted.clone()) {
+                    next.insert(deleted);
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        current = next;
+    }
+    variants
+}
+
+/// Optimal String Alignment (OSA) edit distance between `a` and `b`:
+/// insertions, deletions, substitutions, and adjacent transpositions, each
+/// costing 1. Unlike true Damerau-Levenshtein, OSA forbids editing the same
+/// substring more than once, so it can't e.g. transpose two characters and
+/// then insert one between them: `ca` to `abc` is 3 here, not 2.
+fn optimal_string_alignment_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (la, lb) = (a.len(), b.len());
+    let mut d = vec![vec![0usize; lb + 1]; la + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=la {
+        for j in 1..=lb {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + cost);
+            }
+        }
+    }
+    d[la][lb]
+}
+
+/// Load the dictionary for `lang` from `path`, applying `options` (CLI/config
+/// knobs such as the max edit distance and suggestion count).
+///
+/// If a `{lang}.aff` file sits next to `{lang}.dic`, the pair is loaded as a
+/// standard hunspell dictionary (affix stripping included) - the same files
+/// used by LibreOffice and LanguageTool. Otherwise `{lang}.dic` is read as a
+/// flat wordlist, one word per line.
+pub fn get_dict(path: &Path, lang: &str, options: &DictOptions) -> io::Result<Dict> {
+    let dic_path = path.join(format!("{lang}.dic"));
+    let aff_path = path.join(format!("{lang}.aff"));
+    let mut dict = if aff_path.is_file() {
+        let hunspell_dict = hunspell::load_hunspell(&dic_path, &aff_path)?;
+        let words = hunspell_dict.stems().cloned().collect();
+        Dict::new(words).with_hunspell(hunspell_dict)
+    } else {
+        let content = fs::read_to_string(dic_path)?;
+        let words = content
+            .lines()
+            .map(str::trim)
+            .filter(|word| !word.is_empty())
+            .map(str::to_string)
+            .collect();
+        Dict::new(words)
+    };
+    if let Some(max_edit_distance) = options.max_edit_distance {
+        dict = dict.with_max_edit_distance(max_edit_distance);
+    }
+    if let Some(max_suggestions) = options.max_suggestions {
+        dict = dict.with_max_suggestions(max_suggestions);
+    }
+    if let Some(personal_words_path) = &options.personal_words_path {
+        let words = load_word_list(personal_words_path, options.personal_case_sensitive)?;
+        dict = dict.with_personal_words(words, options.personal_case_sensitive);
+    }
+    if options.accept_compound {
+        let min_part_len = options
+            .min_compound_part_len
+            .unwrap_or(DEFAULT_MIN_COMPOUND_PART_LEN);
+        dict = dict.with_accept_compound(true, min_part_len);
+    }
+    Ok(dict)
+}
+
+/// Load a personal/ignore word list (one word per line) for use with
+/// [`Dict::with_personal_words`]. Words are lowercased while loading when
+/// `case_sensitive` is `false`, to match how they'll be looked up.
+pub fn load_word_list(path: &Path, case_sensitive: bool) -> io::Result<HashSet<String>> {
+    let content = fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            if case_sensitive {
+                word.to_string()
+            } else {
+                word.to_lowercase()
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dict(words: &[&str]) -> Dict {
+        Dict::new(words.iter().map(|word| word.to_string()).collect())
+    }
+
+    /// Create a throwaway flat-wordlist `{lang}.dic` under a fresh temp
+    /// directory, for tests exercising [`get_dict`] itself rather than
+    /// [`Dict`] directly.
+    fn write_wordlist_dict(name: &str, lang: &str, words: &[&str]) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("poexam-dict-test-{name}"));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(format!("{lang}.dic")), words.join("\n")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_get_dict_default_options() {
+        let dir = write_wordlist_dict("default-options", "en", &["typo"]);
+        let dict = get_dict(&dir, "en", &DictOptions::default()).unwrap();
+        assert!(dict.check("typo"));
+        assert!(!dict.suggest("tyypo").is_empty());
+    }
+
+    #[test]
+    fn test_get_dict_applies_max_edit_distance_and_max_suggestions() {
+        let dir = write_wordlist_dict("knobs", "en", &["cat", "car", "can", "cap"]);
+        let options = DictOptions {
+            max_edit_distance: Some(0),
+            max_suggestions: Some(2),
+            ..DictOptions::default()
+        };
+        let dict = get_dict(&dir, "en", &options).unwrap();
+        assert!(dict.suggest("ca").is_empty());
+
+        let options = DictOptions {
+            max_edit_distance: Some(1),
+            max_suggestions: Some(2),
+            ..DictOptions::default()
+        };
+        let dict = get_dict(&dir, "en", &options).unwrap();
+        assert_eq!(dict.suggest("ca").len(), 2);
+    }
+
+    #[test]
+    fn test_get_dict_applies_accept_compound() {
+        let dir = write_wordlist_dict("compound", "en", &["sun", "flower"]);
+        let dict = get_dict(&dir, "en", &DictOptions::default()).unwrap();
+        assert!(!dict.check("sunflower"));
+
+        let options = DictOptions {
+            accept_compound: true,
+            min_compound_part_len: Some(3),
+            ..DictOptions::default()
+        };
+        let dict = get_dict(&dir, "en", &options).unwrap();
+        assert!(dict.check("sunflower"));
+    }
+
+    #[test]
+    fn test_check() {
+        let dict = dict(&["typo", "tested"]);
+        assert!(dict.check("typo"));
+        assert!(!dict.check("tyypo"));
+    }
+
+    #[test]
+    fn test_suggest() {
+        let dict = dict(&["typo", "type", "tested"]);
+        assert_eq!(dict.suggest("tyypo"), vec!["typo", "type"]);
+    }
+
+    #[test]
+    fn test_suggest_correct_word() {
+        let dict = dict(&["typo"]);
+        assert!(dict.suggest("typo").is_empty());
+    }
+
+    #[test]
+    fn test_suggest_no_candidate() {
+        let dict = dict(&["typo"]);
+        assert!(dict.suggest("zzzzzzzzzz").is_empty());
+    }
+
+    #[test]
+    fn test_suggest_max_suggestions() {
+        let dict = dict(&["cat", "car", "can", "cap"]).with_max_suggestions(2);
+        assert_eq!(dict.suggest("ca").len(), 2);
+    }
+
+    #[test]
+    fn test_suggest_max_edit_distance_zero() {
+        let dict = dict(&["typo"]).with_max_edit_distance(0);
+        assert!(dict.suggest("tyypo").is_empty());
+    }
+
+    #[test]
+    fn test_check_personal_words_case_sensitive() {
+        let personal = HashSet::from(["ACME".to_string()]);
+        let dict = dict(&["typo"]).with_personal_words(personal, true);
+        assert!(dict.check("ACME"));
+        assert!(!dict.check("acme"));
+    }
+
+    #[test]
+    fn test_check_personal_words_case_insensitive() {
+        let personal = HashSet::from(["acme".to_string()]);
+        let dict = dict(&["typo"]).with_personal_words(personal, false);
+        assert!(dict.check("ACME"));
+        assert!(dict.check("acme"));
+    }
+
+    #[test]
+    fn test_check_compound_disabled_by_default() {
+        let dict = dict(&["sun", "flower"]);
+        assert!(!dict.check("sunflower"));
+    }
+
+    #[test]
+    fn test_check_compound_enabled() {
+        let dict = dict(&["sun", "flower"]).with_accept_compound(true, 3);
+        assert!(dict.check("sunflower"));
+    }
+
+    #[test]
+    fn test_check_compound_three_parts() {
+        let dict = dict(&["sun", "flower", "bed"]).with_accept_compound(true, 3);
+        assert!(dict.check("sunflowerbed"));
+    }
+
+    #[test]
+    fn test_check_compound_respects_min_part_len() {
+        let dict = dict(&["a", "house"]).with_accept_compound(true, 3);
+        assert!(!dict.check("ahouse"));
+    }
+
+    #[test]
+    fn test_check_compound_rejects_invalid_split() {
+        let dict = dict(&["sun", "flower"]).with_accept_compound(true, 3);
+        assert!(!dict.check("sunlight"));
+    }
+}
@@ -6,10 +6,37 @@ use std::collections::HashSet;
 
 use crate::checker::Checker;
 use crate::diagnostic::Severity;
+use crate::dict::Dict;
 use crate::po::entry::Entry;
 use crate::rules::rule::RuleChecker;
 use crate::words::WordPos;
 
+/// Format a misspelled `word`, appending `dict`'s suggestions when it has
+/// any, e.g. `tyypo (did you mean: typo, type?)`.
+fn with_suggestions(word: &str, dict: &Dict) -> String {
+    let suggestions = dict.suggest(word);
+    if suggestions.is_empty() {
+        word.to_string()
+    } else {
+        format!("{word} (did you mean: {}?)", suggestions.join(", "))
+    }
+}
+
+/// Translator comment directive silencing specific words for an entry, e.g.
+/// `# poexam: ignore-words foo bar`.
+const IGNORE_WORDS_DIRECTIVE: &str = "poexam: ignore-words";
+
+/// Collect the words listed in `# poexam: ignore-words ...` translator
+/// comments on `entry`, so they're skipped only for that entry.
+fn ignored_words(entry: &Entry) -> HashSet<&str> {
+    entry
+        .comments
+        .iter()
+        .filter_map(|comment| comment.trim().strip_prefix(IGNORE_WORDS_DIRECTIVE))
+        .flat_map(str::split_whitespace)
+        .collect()
+}
+
 pub struct SpellingIdRule {}
 
 impl RuleChecker for SpellingIdRule {
@@ -40,14 +67,22 @@ impl RuleChecker for SpellingIdRule {
     /// ```
     ///
     /// Diagnostics reported with severity [`warning`](Severity::Info):
-    /// - `misspelled words in source: xxx`
+    /// - `misspelled words in source: xxx`, with `(did you mean: ...?)`
+    ///   appended to a word when the dictionary has suggestions for it
+    ///
+    /// Words listed in a `# poexam: ignore-words foo bar` translator
+    /// comment on the entry are skipped for that entry only.
     fn check_msg(&self, checker: &mut Checker, entry: &Entry, msgid: &str, msgstr: &str) {
         let mut misspelled_words: Vec<&str> = Vec::new();
         let mut hash_words: HashSet<&str> = HashSet::new();
         let mut pos_words = Vec::new();
         if let Some(dict) = &checker.dict_id {
+            let ignored = ignored_words(entry);
             for (start, end) in WordPos::new(msgid, &entry.format) {
                 let word = &msgid[start..end];
+                if ignored.contains(word) {
+                    continue;
+                }
                 if hash_words.contains(word) {
                     pos_words.push((start, end));
                 } else if !dict.check(word) {
@@ -56,20 +91,21 @@ impl RuleChecker for SpellingIdRule {
                     pos_words.push((start, end));
                 }
             }
-        }
-        if !misspelled_words.is_empty() {
-            misspelled_words.sort_unstable();
-            checker.report_msg(
-                entry,
-                format!(
-                    "misspelled words in source: {}",
-                    misspelled_words.join(", ")
-                ),
-                msgid,
-                &pos_words,
-                msgstr,
-                &[],
-            );
+            if !misspelled_words.is_empty() {
+                misspelled_words.sort_unstable();
+                let words: Vec<String> = misspelled_words
+                    .iter()
+                    .map(|word| with_suggestions(word, dict))
+                    .collect();
+                checker.report_msg(
+                    entry,
+                    format!("misspelled words in source: {}", words.join(", ")),
+                    msgid,
+                    &pos_words,
+                    msgstr,
+                    &[],
+                );
+            }
         }
     }
 }
@@ -104,14 +140,22 @@ impl RuleChecker for SpellingStrRule {
     /// ```
     ///
     /// Diagnostics reported with severity [`warning`](Severity::Info):
-    /// - `misspelled words in translation: xxx`
+    /// - `misspelled words in translation: xxx`, with `(did you mean: ...?)`
+    ///   appended to a word when the dictionary has suggestions for it
+    ///
+    /// Words listed in a `# poexam: ignore-words foo bar` translator
+    /// comment on the entry are skipped for that entry only.
     fn check_msg(&self, checker: &mut Checker, entry: &Entry, msgid: &str, msgstr: &str) {
         let mut misspelled_words: Vec<&str> = Vec::new();
         let mut hash_words: HashSet<&str> = HashSet::new();
         let mut pos_words = Vec::new();
         if let Some(dict) = &checker.dict_str {
+            let ignored = ignored_words(entry);
             for (start, end) in WordPos::new(msgstr, &entry.format) {
                 let word = &msgstr[start..end];
+                if ignored.contains(word) {
+                    continue;
+                }
                 if hash_words.contains(word) {
                     pos_words.push((start, end));
                 } else if !dict.check(word) {
@@ -120,48 +164,75 @@ impl RuleChecker for SpellingStrRule {
                     pos_words.push((start, end));
                 }
             }
-        }
-        if !misspelled_words.is_empty() {
-            misspelled_words.sort_unstable();
-            checker.report_msg(
-                entry,
-                format!(
-                    "misspelled words in translation: {}",
-                    misspelled_words.join(", ")
-                ),
-                msgid,
-                &[],
-                msgstr,
-                &pos_words,
-            );
+            if !misspelled_words.is_empty() {
+                misspelled_words.sort_unstable();
+                let words: Vec<String> = misspelled_words
+                    .iter()
+                    .map(|word| with_suggestions(word, dict))
+                    .collect();
+                checker.report_msg(
+                    entry,
+                    format!("misspelled words in translation: {}", words.join(", ")),
+                    msgid,
+                    &[],
+                    msgstr,
+                    &pos_words,
+                );
+            }
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::fs;
+    use std::path::{Path, PathBuf};
 
     use super::*;
     use crate::{
-        args::DEFAULT_LANG_ID, diagnostic::Diagnostic, dict::get_dict, rules::rule::Rules,
+        args::DEFAULT_LANG_ID,
+        diagnostic::Diagnostic,
+        dict::{get_dict, DictOptions},
+        rules::rule::Rules,
     };
 
     fn check_spelling(content: &str) -> Vec<Diagnostic> {
+        let mut test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_dir.push("resources/test");
+        let dict_id =
+            get_dict(test_dir.as_path(), DEFAULT_LANG_ID, &DictOptions::default()).unwrap();
+        check_spelling_with_dict_id(content, test_dir.as_path(), dict_id)
+    }
+
+    /// Like [`check_spelling`], but with a caller-supplied `dict_id` (e.g.
+    /// one loaded with non-default [`DictOptions`]), for end-to-end coverage
+    /// of dictionary knobs that a `Dict`-level test alone can't exercise.
+    fn check_spelling_with_dict_id(
+        content: &str,
+        path_dicts: &Path,
+        dict_id: Dict,
+    ) -> Vec<Diagnostic> {
         let rules = Rules::new(vec![
             Box::new(SpellingIdRule {}),
             Box::new(SpellingStrRule {}),
         ]);
-        let mut test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        test_dir.push("resources/test");
-        let dict_id = get_dict(test_dir.as_path(), DEFAULT_LANG_ID).unwrap();
         let mut checker = Checker::new(content.as_bytes(), &rules)
-            .with_path_dicts(test_dir.as_path())
+            .with_path_dicts(path_dicts)
             .with_dict_id(Some(&dict_id));
         checker.do_all_checks();
         checker.diagnostics
     }
 
+    /// Create a throwaway directory under the OS temp dir, for tests that
+    /// need their own `.dic`/word-list files instead of the shared
+    /// `resources/test` fixtures.
+    fn write_temp_dict_dir(name: &str, lang: &str, words: &[&str]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("poexam-spelling-test-{name}"));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(format!("{lang}.dic")), words.join("\n")).unwrap();
+        dir
+    }
+
     #[test]
     fn test_spelling_ok() {
         let diags = check_spelling(
@@ -192,13 +263,122 @@ msgstr "ceci est une fôte"
         assert_eq!(diag.severity, Severity::Info);
         assert_eq!(
             diag.message,
-            "misspelled words in source: a, is, this, tyypo"
+            "misspelled words in source: a, is, this, tyypo (did you mean: typo?)"
         );
         let diag = &diags[1];
         assert_eq!(diag.severity, Severity::Info);
         assert_eq!(
             diag.message,
-            "misspelled words in translation: ceci, est, fôte, une"
+            "misspelled words in translation: ceci, est, fôte (did you mean: faute?), une"
+        );
+    }
+
+    #[test]
+    fn test_spelling_ignore_words() {
+        let diags = check_spelling(
+            r#"
+msgid ""
+msgstr "Language: fr\n"
+
+# poexam: ignore-words tyypo fôte
+msgid "this is a tyypo"
+msgstr "ceci est une fôte"
+"#,
+        );
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].message, "misspelled words in source: a, is, this");
+        assert_eq!(
+            diags[1].message,
+            "misspelled words in translation: ceci, est, une"
+        );
+    }
+
+    #[test]
+    fn test_spelling_personal_words_file() {
+        let dir = write_temp_dict_dir("personal-words", "en", &["hello"]);
+        fs::write(dir.join("personal.txt"), "acme\n").unwrap();
+        let options = DictOptions {
+            personal_words_path: Some(dir.join("personal.txt")),
+            personal_case_sensitive: false,
+            ..DictOptions::default()
+        };
+        let dict_id = get_dict(&dir, "en", &options).unwrap();
+
+        let diags = check_spelling_with_dict_id(
+            r#"
+msgid ""
+msgstr "Language: en\n"
+
+msgid "hello ACME"
+msgstr "hello"
+"#,
+            &dir,
+            dict_id,
+        );
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_spelling_without_personal_words_file_still_flags_it() {
+        let dir = write_temp_dict_dir("no-personal-words", "en", &["hello"]);
+        let dict_id = get_dict(&dir, "en", &DictOptions::default()).unwrap();
+
+        let diags = check_spelling_with_dict_id(
+            r#"
+msgid ""
+msgstr "Language: en\n"
+
+msgid "hello ACME"
+msgstr "hello"
+"#,
+            &dir,
+            dict_id,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].message, "misspelled words in source: ACME");
+    }
+
+    #[test]
+    fn test_spelling_accept_compound() {
+        let dir = write_temp_dict_dir("accept-compound", "en", &["sun", "flower"]);
+        let options = DictOptions {
+            accept_compound: true,
+            min_compound_part_len: Some(3),
+            ..DictOptions::default()
+        };
+        let dict_id = get_dict(&dir, "en", &options).unwrap();
+
+        let diags = check_spelling_with_dict_id(
+            r#"
+msgid ""
+msgstr "Language: en\n"
+
+msgid "sunflower"
+msgstr "sun"
+"#,
+            &dir,
+            dict_id,
+        );
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_spelling_without_accept_compound_still_flags_it() {
+        let dir = write_temp_dict_dir("no-accept-compound", "en", &["sun", "flower"]);
+        let dict_id = get_dict(&dir, "en", &DictOptions::default()).unwrap();
+
+        let diags = check_spelling_with_dict_id(
+            r#"
+msgid ""
+msgstr "Language: en\n"
+
+msgid "sunflower"
+msgstr "sun"
+"#,
+            &dir,
+            dict_id,
         );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].message, "misspelled words in source: sunflower");
     }
 }
@@ -2,20 +2,74 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use crate::c_format::get_index_end_c_format;
+use crate::c_format::{
+    get_index_end_brace_format, get_index_end_c_format, get_index_end_python_format,
+    get_index_end_qt_format,
+};
+
+/// Gettext format flags whose placeholders [`WordPos`] knows how to skip.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FormatKind {
+    /// No placeholder skipping (plain text, or an unsupported format flag).
+    None,
+    /// `c-format`: `%05d`, `%llu`, ...
+    C,
+    /// `python-format`: `%(name)s`, plus plain C conversions.
+    Python,
+    /// `python-brace-format`: `{}`, `{name}`, `{0:>8}`, ...
+    PythonBrace,
+    /// `qt-format`: `%1`, `%2`, ...
+    Qt,
+}
+
+impl FormatKind {
+    fn from_str(format: &str) -> Self {
+        match format {
+            "c" => Self::C,
+            "python-format" => Self::Python,
+            "python-brace-format" => Self::PythonBrace,
+            "qt-format" => Self::Qt,
+            _ => Self::None,
+        }
+    }
+
+    /// Byte that starts a placeholder for this format (`%` or `{`), or
+    /// `None` if this format doesn't skip placeholders.
+    fn trigger(self) -> Option<u8> {
+        match self {
+            Self::None => None,
+            Self::C | Self::Python | Self::Qt => Some(b'%'),
+            Self::PythonBrace => Some(b'{'),
+        }
+    }
+
+    /// Index right after the end of the placeholder starting at `pos` (the
+    /// byte right after the trigger character).
+    fn index_end(self, bytes: &[u8], pos: usize, len: usize) -> usize {
+        match self {
+            Self::None => pos,
+            Self::C => get_index_end_c_format(bytes, pos, len),
+            Self::Python => get_index_end_python_format(bytes, pos, len),
+            Self::PythonBrace => get_index_end_brace_format(bytes, pos, len),
+            Self::Qt => get_index_end_qt_format(bytes, pos, len),
+        }
+    }
+}
 
 pub struct WordPos<'a> {
     s: &'a str,
     bytes: &'a [u8],
     len: usize,
-    skip_c_format: bool,
+    format: FormatKind,
     pos: usize,
 }
 
 impl<'a> WordPos<'a> {
     /// Create a new `WordPos` iterator.
     ///
-    /// Argument `format` can be `c` or an empty string.
+    /// Argument `format` is a gettext format flag (`c`, `python-format`,
+    /// `python-brace-format`, `qt-format`) or an empty string; any other
+    /// value is treated like an empty string (no placeholder skipping).
     pub fn new(s: &'a str, format: &str) -> Self {
         let bytes = s.as_bytes();
         let len = bytes.len();
@@ -23,7 +77,7 @@ impl<'a> WordPos<'a> {
             s,
             bytes,
             len,
-            skip_c_format: format == "c",
+            format: FormatKind::from_str(format),
             pos: 0,
         }
     }
@@ -36,33 +90,35 @@ impl Iterator for WordPos<'_> {
         let mut idx_start = None;
         let mut idx_end = None;
         while self.pos < self.len {
-            // Skip C format.
-            if self.skip_c_format && idx_start.is_none() && self.bytes[self.pos] == b'%' {
-                self.pos += 1;
-                if self.pos < self.len && self.bytes[self.pos] == b'%' {
+            // Skip format placeholders.
+            if let Some(trigger) = self.format.trigger() {
+                if idx_start.is_none() && self.bytes[self.pos] == trigger {
                     self.pos += 1;
-                } else {
-                    self.pos = get_index_end_c_format(self.bytes, self.pos, self.len);
-                }
-                if self.pos >= self.len {
-                    return None;
+                    if self.pos < self.len && self.bytes[self.pos] == trigger {
+                        self.pos += 1;
+                    } else {
+                        self.pos = self.format.index_end(self.bytes, self.pos, self.len);
+                    }
+                    if self.pos >= self.len {
+                        return None;
+                    }
+                    continue;
                 }
-            } else {
-                match self.s[self.pos..].chars().next() {
-                    Some(c) => {
-                        let len_c = c.len_utf8();
-                        if c.is_alphanumeric() || (idx_start.is_some() && c == '-') {
-                            if idx_start.is_none() {
-                                idx_start = Some(self.pos);
-                            }
-                            idx_end = Some(self.pos + len_c);
-                        } else if idx_start.is_some() {
-                            break;
+            }
+            match self.s[self.pos..].chars().next() {
+                Some(c) => {
+                    let len_c = c.len_utf8();
+                    if c.is_alphanumeric() || (idx_start.is_some() && c == '-') {
+                        if idx_start.is_none() {
+                            idx_start = Some(self.pos);
                         }
-                        self.pos += len_c;
+                        idx_end = Some(self.pos + len_c);
+                    } else if idx_start.is_some() {
+                        break;
                     }
-                    None => return None,
+                    self.pos += len_c;
                 }
+                None => return None,
             }
         }
         match (idx_start, idx_end) {
@@ -122,6 +178,49 @@ mod tests {
         assert_eq!(&s[pos[3].0..pos[3].1], "42");
     }
 
+    #[test]
+    fn test_python_format() {
+        let s = "Hello %(name)s world";
+        // Do not skip any format.
+        let pos: Vec<_> = WordPos::new(s, "").collect();
+        assert_eq!(pos, vec![(0, 5), (8, 12), (13, 14), (15, 20)]);
+        assert_eq!(&s[pos[1].0..pos[1].1], "name");
+        assert_eq!(&s[pos[2].0..pos[2].1], "s");
+        // Skip python format.
+        let pos: Vec<_> = WordPos::new(s, "python-format").collect();
+        assert_eq!(pos, vec![(0, 5), (15, 20)]);
+        assert_eq!(&s[pos[0].0..pos[0].1], "Hello");
+        assert_eq!(&s[pos[1].0..pos[1].1], "world");
+    }
+
+    #[test]
+    fn test_python_brace_format() {
+        let s = "Hello {name} world";
+        // Do not skip any format.
+        let pos: Vec<_> = WordPos::new(s, "").collect();
+        assert_eq!(pos, vec![(0, 5), (7, 11), (13, 18)]);
+        assert_eq!(&s[pos[1].0..pos[1].1], "name");
+        // Skip python-brace format.
+        let pos: Vec<_> = WordPos::new(s, "python-brace-format").collect();
+        assert_eq!(pos, vec![(0, 5), (13, 18)]);
+        assert_eq!(&s[pos[0].0..pos[0].1], "Hello");
+        assert_eq!(&s[pos[1].0..pos[1].1], "world");
+    }
+
+    #[test]
+    fn test_qt_format() {
+        let s = "Hello %1 world";
+        // Do not skip any format.
+        let pos: Vec<_> = WordPos::new(s, "").collect();
+        assert_eq!(pos, vec![(0, 5), (7, 8), (9, 14)]);
+        assert_eq!(&s[pos[1].0..pos[1].1], "1");
+        // Skip qt format.
+        let pos: Vec<_> = WordPos::new(s, "qt-format").collect();
+        assert_eq!(pos, vec![(0, 5), (9, 14)]);
+        assert_eq!(&s[pos[0].0..pos[0].1], "Hello");
+        assert_eq!(&s[pos[1].0..pos[1].1], "world");
+    }
+
     #[test]
     fn test_unicode() {
         let s = "héllo, мир! %lld 你好";